@@ -0,0 +1,128 @@
+//! Decoding and encoding of [`Body::EncodedString`]'s `encoding` label.
+
+use std::fmt;
+use std::io::{Read, Write};
+
+use base64::Engine;
+
+use crate::Body;
+
+/// An error returned when a [`Body::EncodedString`] cannot be decoded by
+/// [`Body::decoded_bytes`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum DecodeError {
+    /// The `encoding` label isn't one this crate knows how to decode.
+    UnknownEncoding(String),
+    /// The string was not valid data for its declared `encoding`.
+    Invalid {
+        /// The encoding that failed to decode the string.
+        encoding: String,
+        /// The underlying decode error.
+        reason: String,
+    },
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownEncoding(e) => write!(f, "unknown body encoding {e:?}"),
+            Self::Invalid { encoding, reason } => {
+                write!(f, "invalid {encoding} body: {reason}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+impl Body {
+    /// Decode this body to its raw bytes, acting on the `encoding` declared by
+    /// [`Body::EncodedString`].
+    ///
+    /// `base64` is decoded with the [`base64`] crate directly. `gzip` and `deflate` are first
+    /// base64-decoded and the result inflated with [`flate2`] — the compressed bytes themselves
+    /// aren't valid UTF-8, so they can't be stored in the `string` field any other way, and this
+    /// is the wire form [`Body::encode`] produces. An absent or empty encoding is returned as the
+    /// string's raw UTF-8 bytes. Any other encoding label yields [`DecodeError::UnknownEncoding`].
+    /// Every other `Body` variant has no encoding to act on, so it's returned as its
+    /// [`Display`](fmt::Display) representation's raw bytes.
+    pub fn decoded_bytes(&self) -> Result<Vec<u8>, DecodeError> {
+        match self {
+            Self::EncodedString { encoding, string } => {
+                decode(encoding.as_deref().unwrap_or(""), string)
+            }
+            other => Ok(other.to_string().into_bytes()),
+        }
+    }
+
+    /// Build a [`Body::EncodedString`] by encoding `bytes` with the named `encoding`.
+    ///
+    /// `base64` stores `bytes` base64-encoded directly. `gzip` and `deflate` compress `bytes`
+    /// first and then base64-encode the compressed output, since the raw compressed bytes aren't
+    /// valid UTF-8 and couldn't otherwise be stored in the `string` field; [`Body::decoded_bytes`]
+    /// reverses this exactly, so `Body::encode(bytes, enc).decoded_bytes() == Ok(bytes.to_vec())`
+    /// for all three. Anything else is stored as the raw UTF-8-lossy string.
+    pub fn encode(bytes: &[u8], encoding: &str) -> Body {
+        Body::EncodedString {
+            encoding: Some(encoding.to_string()),
+            string: encode_bytes(bytes, encoding),
+        }
+    }
+}
+
+fn decode(encoding: &str, string: &str) -> Result<Vec<u8>, DecodeError> {
+    let invalid = |reason: String| DecodeError::Invalid {
+        encoding: encoding.to_string(),
+        reason,
+    };
+
+    match encoding {
+        "" => Ok(string.as_bytes().to_vec()),
+        "base64" => base64::engine::general_purpose::STANDARD
+            .decode(string)
+            .map_err(|e| invalid(e.to_string())),
+        "gzip" => {
+            let compressed = base64::engine::general_purpose::STANDARD
+                .decode(string)
+                .map_err(|e| invalid(e.to_string()))?;
+            let mut out = Vec::new();
+            flate2::read::GzDecoder::new(compressed.as_slice())
+                .read_to_end(&mut out)
+                .map_err(|e| invalid(e.to_string()))?;
+            Ok(out)
+        }
+        "deflate" => {
+            let compressed = base64::engine::general_purpose::STANDARD
+                .decode(string)
+                .map_err(|e| invalid(e.to_string()))?;
+            let mut out = Vec::new();
+            flate2::read::DeflateDecoder::new(compressed.as_slice())
+                .read_to_end(&mut out)
+                .map_err(|e| invalid(e.to_string()))?;
+            Ok(out)
+        }
+        other => Err(DecodeError::UnknownEncoding(other.to_string())),
+    }
+}
+
+fn encode_bytes(bytes: &[u8], encoding: &str) -> String {
+    match encoding {
+        "base64" => base64::engine::general_purpose::STANDARD.encode(bytes),
+        "gzip" => {
+            let mut encoder =
+                flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+            encoder.write_all(bytes).expect("writing to a Vec cannot fail");
+            let compressed = encoder.finish().expect("writing to a Vec cannot fail");
+            base64::engine::general_purpose::STANDARD.encode(compressed)
+        }
+        "deflate" => {
+            let mut encoder =
+                flate2::write::DeflateEncoder::new(Vec::new(), flate2::Compression::default());
+            encoder.write_all(bytes).expect("writing to a Vec cannot fail");
+            let compressed = encoder.finish().expect("writing to a Vec cannot fail");
+            base64::engine::general_purpose::STANDARD.encode(compressed)
+        }
+        _ => String::from_utf8_lossy(bytes).into_owned(),
+    }
+}
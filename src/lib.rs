@@ -66,7 +66,16 @@ use void::Void;
 pub use chrono;
 pub use url;
 
+#[cfg(feature = "codecs")]
+mod codecs;
 mod datetime;
+mod matching;
+mod streaming;
+
+#[cfg(feature = "codecs")]
+pub use codecs::DecodeError;
+pub use matching::MatchOptions;
+pub use streaming::StreamingCassette;
 
 /// An HTTP Headers type.
 pub type Headers = HashMap<String, Vec<String>>;
@@ -153,6 +162,14 @@ pub enum Body {
     /// still have to do that yourself.
     #[cfg(feature = "json")]
     Json(serde_json::Value),
+
+    /// An `application/x-www-form-urlencoded` body, eg `"body": { "form": { "a": ["1"] } }`.
+    ///
+    /// Matches another `Form` body with the same keys and values, or a [`Body::String`] whose
+    /// query-string pairs are equal when compared as an order-insensitive multiset, so that
+    /// `a=1&b=2` is considered equal to `b=2&a=1`.
+    #[cfg(feature = "form")]
+    Form(HashMap<String, Vec<String>>),
 }
 
 impl std::fmt::Display for Body {
@@ -168,10 +185,40 @@ impl std::fmt::Display for Body {
             Self::Matchers(m) => f.debug_list().entries(m.iter()).finish(),
             #[cfg(feature = "json")]
             Self::Json(j) => f.write_str(&serde_json::to_string(j).expect("invalid JSON body")),
+            #[cfg(feature = "form")]
+            Self::Form(form) => f.write_str(&form_to_query_string(form)),
         }
     }
 }
 
+#[cfg(feature = "form")]
+fn form_to_query_string(form: &HashMap<String, Vec<String>>) -> String {
+    let mut pairs = form_pairs(form);
+    pairs.sort();
+
+    let mut ser = url::form_urlencoded::Serializer::new(String::new());
+    for (k, v) in &pairs {
+        ser.append_pair(k, v);
+    }
+    ser.finish()
+}
+
+#[cfg(feature = "form")]
+fn form_pairs(form: &HashMap<String, Vec<String>>) -> Vec<(String, String)> {
+    form.iter()
+        .flat_map(|(k, vs)| vs.iter().map(move |v| (k.clone(), v.clone())))
+        .collect()
+}
+
+#[cfg(feature = "form")]
+fn query_string_pairs(s: &str) -> Vec<(String, String)> {
+    let mut pairs: Vec<(String, String)> = url::form_urlencoded::parse(s.as_bytes())
+        .into_owned()
+        .collect();
+    pairs.sort();
+    pairs
+}
+
 impl<'de> Deserialize<'de> for Body {
     fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
         struct BodyVisitor(PhantomData<fn() -> Body>);
@@ -215,6 +262,8 @@ impl<'de> Deserialize<'de> for Body {
                     Some("matches") => Ok(Body::Matchers(map.next_value()?)),
                     #[cfg(feature = "json")]
                     Some("json") => Ok(Body::Json(map.next_value()?)),
+                    #[cfg(feature = "form")]
+                    Some("form") => Ok(Body::Form(map.next_value()?)),
                     Some(k) => Err(M::Error::unknown_field(
                         k,
                         &[
@@ -224,19 +273,45 @@ impl<'de> Deserialize<'de> for Body {
                             "matches",
                             #[cfg(feature = "json")]
                             "json",
+                            #[cfg(feature = "form")]
+                            "form",
                         ],
                     )),
                     None => {
                         // OK this is starting to get silly
-                        #[cfg(all(feature = "matching", feature = "json"))]
+                        #[cfg(all(feature = "matching", feature = "json", feature = "form"))]
+                        let fields = "matches, json, form, encoding, or string";
+                        #[cfg(all(feature = "matching", feature = "json", not(feature = "form")))]
                         let fields = "matches, json, encoding, or string";
-                        #[cfg(all(feature = "matching", not(feature = "json")))]
+                        #[cfg(all(feature = "matching", not(feature = "json"), feature = "form"))]
+                        let fields = "matches, form, encoding, or string";
+                        #[cfg(all(
+                            feature = "matching",
+                            not(feature = "json"),
+                            not(feature = "form")
+                        ))]
                         let fields = "matches, encoding, or string";
-                        #[cfg(all(not(feature = "matching"), feature = "json"))]
+                        #[cfg(all(not(feature = "matching"), feature = "json", feature = "form"))]
+                        let fields = "json, form, encoding, or string";
+                        #[cfg(all(
+                            not(feature = "matching"),
+                            feature = "json",
+                            not(feature = "form")
+                        ))]
                         let fields = "json, encoding, or string";
+                        #[cfg(all(
+                            not(feature = "matching"),
+                            not(feature = "json"),
+                            feature = "form"
+                        ))]
+                        let fields = "form, encoding, or string";
                         // Yes, DeMorgan says there's a better way to do this, but it's visually
                         // more similar to the previous versions, so it's more readable, IMO
-                        #[cfg(all(not(feature = "matching"), not(feature = "json")))]
+                        #[cfg(all(
+                            not(feature = "matching"),
+                            not(feature = "json"),
+                            not(feature = "form")
+                        ))]
                         let fields = "encoding or string";
 
                         Err(M::Error::missing_field(fields))
@@ -271,6 +346,12 @@ impl Serialize for Body {
                 map.serialize_entry("json", j)?;
                 map.end()
             }
+            #[cfg(feature = "form")]
+            Self::Form(form) => {
+                let mut map = ser.serialize_map(Some(1))?;
+                map.serialize_entry("form", form)?;
+                map.end()
+            }
         }
     }
 }
@@ -285,17 +366,29 @@ impl PartialEq for Body {
                 Self::Matchers(_) => other.eq(self),
                 #[cfg(feature = "json")]
                 Self::Json(j) => serde_json::to_string(j).expect("invalid JSON body") == *s,
+                #[cfg(feature = "form")]
+                Self::Form(_) => other.eq(self),
             },
             Self::EncodedString { encoding, string } => match other {
                 Self::String(s) => encoding.is_none() && s == string,
                 Self::EncodedString {
                     encoding: oe,
                     string: os,
-                } => encoding == oe && string == os,
+                } => {
+                    #[cfg(feature = "codecs")]
+                    if encoding == oe {
+                        if let (Ok(a), Ok(b)) = (self.decoded_bytes(), other.decoded_bytes()) {
+                            return a == b;
+                        }
+                    }
+                    encoding == oe && string == os
+                }
                 #[cfg(feature = "matching")]
                 Self::Matchers(_) => false,
                 #[cfg(feature = "json")]
                 Self::Json(_) => false,
+                #[cfg(feature = "form")]
+                Self::Form(_) => false,
             },
             #[cfg(feature = "matching")]
             Self::Matchers(matchers) => match other {
@@ -308,9 +401,26 @@ impl PartialEq for Body {
                     let s = serde_json::to_string(j).expect("invalid JSON body");
                     matchers.iter().all(|m| m.matches(&s))
                 }
+                #[cfg(feature = "form")]
+                Self::Form(form) => matchers.iter().all(|m| m.matches(&form_to_query_string(form))),
             },
             #[cfg(feature = "json")]
             Self::Json(_) => other.eq(self),
+            #[cfg(feature = "form")]
+            Self::Form(form) => match other {
+                Self::String(s) => {
+                    let mut pairs = form_pairs(form);
+                    pairs.sort();
+                    pairs == query_string_pairs(s)
+                }
+                Self::EncodedString { .. } => false,
+                #[cfg(feature = "matching")]
+                Self::Matchers(_) => other.eq(self),
+                #[cfg(feature = "json")]
+                Self::Json(_) => false,
+                #[cfg(feature = "form")]
+                Self::Form(o) => form == o,
+            },
         }
     }
 }
@@ -333,6 +443,16 @@ pub enum BodyMatcher {
     Regex(Regex),
 }
 
+impl PartialEq for BodyMatcher {
+    fn eq(&self, other: &Self) -> bool {
+        match self {
+            Self::Substring(a) => matches!(other, Self::Substring(b) if a == b),
+            #[cfg(feature = "regex")]
+            Self::Regex(a) => matches!(other, Self::Regex(b) if a.as_str() == b.as_str()),
+        }
+    }
+}
+
 #[cfg(feature = "regex")]
 fn parse_regex<'de, D: Deserializer<'de>>(d: D) -> Result<Regex, D::Error> {
     struct RegexVisitor(PhantomData<fn() -> Regex>);
@@ -392,14 +512,98 @@ pub struct Status {
 /// A recorded HTTP Request.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Request {
-    /// The Request URI.
-    pub uri: Url,
+    /// The Request URI, or a set of matchers it must satisfy.
+    pub uri: UriMatch,
     /// The Request body.
     pub body: Body,
     /// The Request method.
     pub method: Method,
     /// The Request headers.
     pub headers: Headers,
+
+    /// Fuzzy matchers for specific header values, keyed by header name.
+    ///
+    /// When present for a header name, the incoming request's values for that header (joined
+    /// with `", "`) are checked against the matchers instead of requiring exact equality.
+    #[cfg(feature = "matching")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub header_matchers: Option<HashMap<String, Vec<BodyMatcher>>>,
+}
+
+/// The Request URI: either a concrete [`Url`], or a set of [`BodyMatcher`]s the incoming URI must
+/// satisfy.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum UriMatch {
+    /// A concrete URI, eg `"uri": "http://localhost:7777/foo"`. Matches only that URI; whether
+    /// its query parameters must also appear in the same order is set by
+    /// [`MatchOptions::ignore_query_order`].
+    Url(Url),
+    /// A series of [`BodyMatcher`] instances, eg `"uri": { "matches": [ { "regex": "/users/\\d+" } ] }`.
+    /// All specified matchers must pass in order for the incoming URI to be deemed to match.
+    #[cfg(feature = "matching")]
+    Matchers(Vec<BodyMatcher>),
+}
+
+impl PartialEq for UriMatch {
+    fn eq(&self, other: &Self) -> bool {
+        match self {
+            Self::Url(a) => matches!(other, Self::Url(b) if a == b),
+            #[cfg(feature = "matching")]
+            Self::Matchers(_) => false,
+        }
+    }
+}
+
+impl From<Url> for UriMatch {
+    fn from(url: Url) -> Self {
+        Self::Url(url)
+    }
+}
+
+impl<'de> Deserialize<'de> for UriMatch {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct UriMatchVisitor(PhantomData<fn() -> UriMatch>);
+
+        impl<'de> Visitor<'de> for UriMatchVisitor {
+            type Value = UriMatch;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a URI string or a map of matchers")
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<UriMatch, E> {
+                Url::parse(value)
+                    .map(UriMatch::Url)
+                    .map_err(|e| E::custom(format!("invalid URI: {e}")))
+            }
+
+            #[cfg(feature = "matching")]
+            fn visit_map<M: MapAccess<'de>>(self, mut map: M) -> Result<UriMatch, M::Error> {
+                match map.next_key::<String>()?.as_deref() {
+                    Some("matches") => Ok(UriMatch::Matchers(map.next_value()?)),
+                    Some(k) => Err(M::Error::unknown_field(k, &["matches"])),
+                    None => Err(M::Error::missing_field("matches")),
+                }
+            }
+        }
+
+        deserializer.deserialize_any(UriMatchVisitor(PhantomData))
+    }
+}
+
+impl Serialize for UriMatch {
+    fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Url(u) => ser.serialize_str(u.as_str()),
+            #[cfg(feature = "matching")]
+            Self::Matchers(m) => {
+                let mut map = ser.serialize_map(Some(1))?;
+                map.serialize_entry("matches", m)?;
+                map.end()
+            }
+        }
+    }
 }
 
 /// An HTTP method.
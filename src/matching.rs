@@ -0,0 +1,164 @@
+//! The playback/request-matching engine: deciding whether an incoming [`Request`] corresponds to
+//! a previously recorded one.
+
+use std::collections::HashSet;
+
+use url::Url;
+
+use crate::{Cassette, HttpInteraction, Request, UriMatch};
+
+/// Options controlling how [`Cassette::find_interaction`] decides whether an incoming [`Request`]
+/// matches a recorded one.
+///
+/// The default set of options (method, URI and body compared; headers ignored) mirrors VCR's own
+/// default `:match_requests_on`.
+#[derive(Debug, Clone)]
+pub struct MatchOptions {
+    /// Compare the HTTP method. Comparison is always case-insensitive.
+    pub method: bool,
+    /// Compare the request URI.
+    pub uri: bool,
+    /// Compare the request body.
+    pub body: bool,
+    /// Compare request headers.
+    pub headers: bool,
+    /// When [`headers`](Self::headers) is `true`, restrict header comparison to this list of
+    /// header names (matched case-insensitively) instead of comparing every header present on
+    /// either side. `None` compares the union of all headers present on either request.
+    pub header_allowlist: Option<Vec<String>>,
+    /// When comparing the URI, ignore the order of query-string parameters by collecting them
+    /// into a multiset instead of comparing the query string byte-for-byte.
+    pub ignore_query_order: bool,
+}
+
+impl Default for MatchOptions {
+    fn default() -> Self {
+        Self {
+            method: true,
+            uri: true,
+            body: true,
+            headers: false,
+            header_allowlist: None,
+            ignore_query_order: true,
+        }
+    }
+}
+
+impl MatchOptions {
+    fn matches(&self, recorded: &Request, incoming: &Request) -> bool {
+        if self.method && !recorded.method.as_str().eq_ignore_ascii_case(incoming.method.as_str())
+        {
+            return false;
+        }
+
+        if self.uri && !self.uri_matches(&recorded.uri, &incoming.uri) {
+            return false;
+        }
+
+        if self.body && recorded.body != incoming.body {
+            return false;
+        }
+
+        if self.headers && !self.headers_match(recorded, incoming) {
+            return false;
+        }
+
+        true
+    }
+
+    fn uri_matches(&self, recorded: &UriMatch, incoming: &UriMatch) -> bool {
+        match recorded {
+            UriMatch::Url(recorded_url) => match incoming {
+                UriMatch::Url(incoming_url) => self.urls_match(recorded_url, incoming_url),
+                #[cfg(feature = "matching")]
+                UriMatch::Matchers(_) => false,
+            },
+            #[cfg(feature = "matching")]
+            UriMatch::Matchers(matchers) => match incoming {
+                UriMatch::Url(incoming_url) => {
+                    matchers.iter().all(|m| m.matches(incoming_url.as_str()))
+                }
+                #[cfg(feature = "matching")]
+                UriMatch::Matchers(_) => false,
+            },
+        }
+    }
+
+    fn urls_match(&self, recorded: &Url, incoming: &Url) -> bool {
+        if !self.ignore_query_order {
+            return recorded == incoming;
+        }
+
+        let mut recorded_without_query = recorded.clone();
+        recorded_without_query.set_query(None);
+        let mut incoming_without_query = incoming.clone();
+        incoming_without_query.set_query(None);
+        if recorded_without_query != incoming_without_query {
+            return false;
+        }
+
+        let mut recorded_pairs: Vec<_> = recorded.query_pairs().into_owned().collect();
+        let mut incoming_pairs: Vec<_> = incoming.query_pairs().into_owned().collect();
+        recorded_pairs.sort();
+        incoming_pairs.sort();
+        recorded_pairs == incoming_pairs
+    }
+
+    fn headers_match(&self, recorded: &Request, incoming: &Request) -> bool {
+        let names: HashSet<String> = match &self.header_allowlist {
+            Some(allowlist) => allowlist.iter().map(|h| h.to_ascii_lowercase()).collect(),
+            None => recorded
+                .headers
+                .keys()
+                .chain(incoming.headers.keys())
+                .map(|h| h.to_ascii_lowercase())
+                .collect(),
+        };
+
+        names.into_iter().all(|name| {
+            #[cfg(feature = "matching")]
+            if let Some(matchers) = recorded
+                .header_matchers
+                .as_ref()
+                .and_then(|m| header_values(m, &name))
+            {
+                let joined = header_values(&incoming.headers, &name)
+                    .map(|v| v.join(", "))
+                    .unwrap_or_default();
+                return matchers.iter().all(|m| m.matches(&joined));
+            }
+
+            header_values(&recorded.headers, &name) == header_values(&incoming.headers, &name)
+        })
+    }
+}
+
+fn header_values<'h, V>(
+    headers: &'h std::collections::HashMap<String, V>,
+    name: &str,
+) -> Option<&'h V> {
+    headers
+        .iter()
+        .find(|(k, _)| k.eq_ignore_ascii_case(name))
+        .map(|(_, v)| v)
+}
+
+impl Cassette {
+    /// Find the first recorded interaction whose request matches `req` under the rules specified
+    /// by `opts`.
+    pub fn find_interaction(&self, req: &Request, opts: &MatchOptions) -> Option<&HttpInteraction> {
+        self.find_interaction_index(req, opts)
+            .map(|i| &self.http_interactions[i])
+    }
+
+    /// As [`Cassette::find_interaction`], but returns the matched interaction's index within
+    /// [`http_interactions`](Cassette::http_interactions) rather than a reference.
+    ///
+    /// This is useful for callers implementing VCR's `once`/`all` "play count" semantics, who
+    /// need to track which interactions have already been replayed.
+    pub fn find_interaction_index(&self, req: &Request, opts: &MatchOptions) -> Option<usize> {
+        self.http_interactions
+            .iter()
+            .position(|interaction| opts.matches(&interaction.request, req))
+    }
+}
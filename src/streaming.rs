@@ -0,0 +1,324 @@
+//! A lazy, read-only alternative to deserializing a whole [`Cassette`] up front.
+//!
+//! [`Cassette::stream_interactions`] hand-scans the top-level `{ "http_interactions": [...],
+//! "recorded_with": ... }` object directly off an [`io::Read`], so that only one interaction's
+//! raw JSON is ever buffered at a time; `serde_json` only decodes that one interaction into a
+//! full [`HttpInteraction`] when the consumer pulls it from the iterator. This is what makes it
+//! safe to use on multi-megabyte cassettes: memory use is bounded by the largest single
+//! interaction, not the whole file.
+
+use std::io::{self, BufRead, BufReader, Read};
+
+use serde::de::Error as _;
+
+use crate::{Cassette, HttpInteraction, RecorderId};
+
+/// A cassette whose interactions are decoded lazily, one at a time, as returned by
+/// [`Cassette::stream_interactions`].
+///
+/// Because the underlying reader is consumed incrementally and only one interaction is ever
+/// buffered, [`recorded_with`](Self::recorded_with) may not be known until the `http_interactions`
+/// array has been fully iterated — the [VCR Cassette
+/// format](https://relishapp.com/vcr/vcr/v/6-0-0/docs/cassettes/cassette-format) writes
+/// `recorded_with` *after* `http_interactions`, so a genuinely streaming reader can only learn it
+/// once the array is behind it.
+#[derive(Debug)]
+pub struct StreamingCassette<R> {
+    reader: BufReader<R>,
+    recorded_with: Option<RecorderId>,
+    state: State,
+}
+
+#[derive(Debug)]
+enum State {
+    InArray { first: bool },
+    Done,
+}
+
+enum ObjectField {
+    HttpInteractions,
+    End,
+}
+
+impl<R> StreamingCassette<R> {
+    /// The identifier of the library which created the recording, once it's been seen.
+    ///
+    /// Returns `None` until the field has actually been scanned off the reader — which, for a
+    /// canonically-ordered cassette, means not until the `http_interactions` array has been
+    /// fully iterated. See the type-level docs for why.
+    pub fn recorded_with(&self) -> Option<&str> {
+        self.recorded_with.as_deref()
+    }
+}
+
+impl<R: Read> StreamingCassette<R> {
+    fn advance_array(&mut self, first: bool) -> Result<bool, serde_json::Error> {
+        skip_whitespace(&mut self.reader).map_err(io_err)?;
+        if !first {
+            match read_byte(&mut self.reader).map_err(io_err)? {
+                b',' => skip_whitespace(&mut self.reader).map_err(io_err)?,
+                b']' => return self.finish_array(),
+                b => return Err(unexpected_byte(b)),
+            }
+        }
+
+        if peek_byte(&mut self.reader).map_err(io_err)? == Some(b']') {
+            read_byte(&mut self.reader).map_err(io_err)?;
+            return self.finish_array();
+        }
+
+        Ok(false)
+    }
+
+    /// Called having just consumed the `]` that closes `http_interactions`; resumes scanning the
+    /// remaining top-level object fields (only `recorded_with` is meaningful) to completion.
+    fn finish_array(&mut self) -> Result<bool, serde_json::Error> {
+        skip_whitespace(&mut self.reader).map_err(io_err)?;
+        match read_byte(&mut self.reader).map_err(io_err)? {
+            b',' => {
+                self.state = match next_field(&mut self.reader, &mut self.recorded_with)? {
+                    ObjectField::HttpInteractions => State::InArray { first: true },
+                    ObjectField::End => State::Done,
+                };
+            }
+            b'}' => self.state = State::Done,
+            b => return Err(unexpected_byte(b)),
+        }
+        Ok(true)
+    }
+}
+
+impl<R: Read> Iterator for StreamingCassette<R> {
+    type Item = Result<HttpInteraction, serde_json::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let first = match &mut self.state {
+                State::Done => return None,
+                State::InArray { first } => std::mem::replace(first, false),
+            };
+
+            match self.advance_array(first) {
+                Ok(true) => continue, // the array just closed; state was updated, loop to re-check it
+                Ok(false) => {
+                    let mut raw = Vec::new();
+                    return Some(match scan_value(&mut self.reader, &mut raw) {
+                        Ok(()) => serde_json::from_slice(&raw),
+                        Err(e) => {
+                            self.state = State::Done;
+                            Err(io_err(e))
+                        }
+                    });
+                }
+                Err(e) => {
+                    self.state = State::Done;
+                    return Some(Err(e));
+                }
+            }
+        }
+    }
+}
+
+impl Cassette {
+    /// Parse `reader` into a [`StreamingCassette`], deferring the decode of each
+    /// [`HttpInteraction`] until it's pulled from the returned iterator, and never buffering more
+    /// than one interaction's raw JSON at a time.
+    ///
+    /// This is an additive, read-only counterpart to deserializing a [`Cassette`] directly (eg
+    /// via `serde_json::from_str::<Cassette>`); use it when a cassette is large enough that
+    /// buffering the whole file, or eagerly decoding every interaction's body and headers, is
+    /// wasteful for a playback-only consumer.
+    ///
+    /// Note this returns a `Result` rather than an `impl Iterator` directly: the top-level object
+    /// header (`{ "http_interactions": [ ... `) is scanned eagerly so malformed input is reported
+    /// immediately, instead of surfacing as the first item pulled from the iterator.
+    pub fn stream_interactions<R: Read>(
+        reader: R,
+    ) -> Result<StreamingCassette<R>, serde_json::Error> {
+        let mut reader = BufReader::new(reader);
+        skip_whitespace(&mut reader).map_err(io_err)?;
+        expect_byte(&mut reader, b'{')?;
+
+        let mut recorded_with = None;
+        let state = match next_field(&mut reader, &mut recorded_with)? {
+            ObjectField::HttpInteractions => State::InArray { first: true },
+            ObjectField::End => State::Done,
+        };
+
+        Ok(StreamingCassette {
+            reader,
+            recorded_with,
+            state,
+        })
+    }
+}
+
+/// Scans object fields (`"key": value` pairs separated by `,`) until either the
+/// `http_interactions` key is found — at which point its opening `[` has been consumed and control
+/// returns so the caller can stream the array — or the object closes with `}`. Any `recorded_with`
+/// value seen along the way is stashed into `recorded_with`; every other field is scanned and
+/// discarded.
+fn next_field<R: Read>(
+    r: &mut BufReader<R>,
+    recorded_with: &mut Option<RecorderId>,
+) -> Result<ObjectField, serde_json::Error> {
+    loop {
+        skip_whitespace(r).map_err(io_err)?;
+        if peek_byte(r).map_err(io_err)? == Some(b'}') {
+            read_byte(r).map_err(io_err)?;
+            return Ok(ObjectField::End);
+        }
+
+        expect_byte(r, b'"')?;
+        let key = read_quoted_string_body(r)?;
+        skip_whitespace(r).map_err(io_err)?;
+        expect_byte(r, b':')?;
+        skip_whitespace(r).map_err(io_err)?;
+
+        if key == "http_interactions" {
+            expect_byte(r, b'[')?;
+            return Ok(ObjectField::HttpInteractions);
+        }
+
+        let mut raw = Vec::new();
+        scan_value(r, &mut raw).map_err(io_err)?;
+        if key == "recorded_with" {
+            *recorded_with = Some(serde_json::from_slice(&raw)?);
+        }
+
+        skip_whitespace(r).map_err(io_err)?;
+        match read_byte(r).map_err(io_err)? {
+            b',' => continue,
+            b'}' => return Ok(ObjectField::End),
+            b => return Err(unexpected_byte(b)),
+        }
+    }
+}
+
+fn read_quoted_string_body<R: Read>(r: &mut BufReader<R>) -> Result<String, serde_json::Error> {
+    let mut raw = vec![b'"'];
+    scan_string_tail(r, &mut raw).map_err(io_err)?;
+    serde_json::from_slice(&raw)
+}
+
+fn expect_byte<R: Read>(r: &mut BufReader<R>, expected: u8) -> Result<(), serde_json::Error> {
+    skip_whitespace(r).map_err(io_err)?;
+    match read_byte(r).map_err(io_err)? {
+        b if b == expected => Ok(()),
+        b => Err(unexpected_byte(b)),
+    }
+}
+
+/// Reads one complete JSON value (of any type) from `r`, appending its raw bytes to `out`. Used
+/// both to capture the bytes of a single `http_interactions` element for deferred decoding, and
+/// to skip over field values this reader doesn't care about.
+fn scan_value<R: Read>(r: &mut BufReader<R>, out: &mut Vec<u8>) -> io::Result<()> {
+    skip_whitespace(r)?;
+    let b = read_byte(r)?;
+    out.push(b);
+    match b {
+        b'"' => scan_string_tail(r, out),
+        b'{' | b'[' => scan_container(r, out),
+        b't' => scan_literal_tail(r, out, 3), // rue
+        b'f' => scan_literal_tail(r, out, 4), // alse
+        b'n' => scan_literal_tail(r, out, 3), // ull
+        _ => scan_number_tail(r, out),
+    }
+}
+
+/// Reads the remainder of a `{...}` or `[...]` value whose opening bracket has already been read
+/// and pushed to `out`, tracking nesting depth so it stops at the matching close.
+fn scan_container<R: Read>(r: &mut BufReader<R>, out: &mut Vec<u8>) -> io::Result<()> {
+    let mut depth: u32 = 1;
+    let mut in_string = false;
+    loop {
+        let b = read_byte(r)?;
+        out.push(b);
+        if in_string {
+            match b {
+                b'\\' => out.push(read_byte(r)?),
+                b'"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match b {
+            b'"' => in_string = true,
+            b'{' | b'[' => depth += 1,
+            b'}' | b']' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Ok(());
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Reads the remainder of a string whose opening `"` has already been read (and, if wanted,
+/// pushed to `out` by the caller), through and including the closing `"`.
+fn scan_string_tail<R: Read>(r: &mut BufReader<R>, out: &mut Vec<u8>) -> io::Result<()> {
+    loop {
+        let b = read_byte(r)?;
+        out.push(b);
+        match b {
+            b'\\' => out.push(read_byte(r)?),
+            b'"' => return Ok(()),
+            _ => {}
+        }
+    }
+}
+
+fn scan_literal_tail<R: Read>(
+    r: &mut BufReader<R>,
+    out: &mut Vec<u8>,
+    remaining: usize,
+) -> io::Result<()> {
+    for _ in 0..remaining {
+        out.push(read_byte(r)?);
+    }
+    Ok(())
+}
+
+fn scan_number_tail<R: Read>(r: &mut BufReader<R>, out: &mut Vec<u8>) -> io::Result<()> {
+    loop {
+        match peek_byte(r)? {
+            Some(b @ (b'0'..=b'9' | b'.' | b'e' | b'E' | b'+' | b'-')) => {
+                r.consume(1);
+                out.push(b);
+            }
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn skip_whitespace<R: Read>(r: &mut BufReader<R>) -> io::Result<()> {
+    loop {
+        match peek_byte(r)? {
+            Some(b) if b.is_ascii_whitespace() => r.consume(1),
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn read_byte<R: Read>(r: &mut BufReader<R>) -> io::Result<u8> {
+    let mut buf = [0u8; 1];
+    r.read_exact(&mut buf)?;
+    Ok(buf[0])
+}
+
+fn peek_byte<R: Read>(r: &mut BufReader<R>) -> io::Result<Option<u8>> {
+    Ok(r.fill_buf()?.first().copied())
+}
+
+fn unexpected_byte(b: u8) -> serde_json::Error {
+    serde_json::Error::custom(format!(
+        "unexpected byte {:?} while scanning cassette",
+        b as char
+    ))
+}
+
+fn io_err(e: io::Error) -> serde_json::Error {
+    serde_json::Error::custom(e)
+}